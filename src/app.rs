@@ -1,24 +1,28 @@
+use crate::context_menu::ContextMenu;
+use crate::expr;
 use crate::input_mode::InputMode;
+use crate::theme::Theme;
 use color_eyre::Result;
-use ratatui::prelude::{Style, Stylize};
 use ratatui::{
+    backend::Backend,
     crossterm::event::{self, Event, KeyCode, KeyEventKind},
-    layout::{Constraint, Layout, Position},
-    style::{Color, Modifier, Style as RatatuiStyle},
+    layout::{Constraint, Layout, Position, Rect},
+    style::{Modifier, Style},
     text::{Line, Span, Text},
     widgets::{Block, List, ListItem, Paragraph},
-    DefaultTerminal, Frame as RatatuiFrame,
+    Frame as RatatuiFrame, Terminal,
 };
 use tui_big_text::{BigText, PixelSize};
 
 pub struct App {
     input: String,
-    first_number: Option<i32>,
-    second_number: Option<i32>,
-    operator: Option<String>,
     character_index: usize,
     input_mode: InputMode,
     messages: Vec<String>,
+    history: Vec<String>,
+    history_index: Option<usize>,
+    context_menu: ContextMenu,
+    theme: Theme,
 }
 
 impl Default for App {
@@ -32,12 +36,13 @@ impl App {
     pub const fn new() -> Self {
         Self {
             input: String::new(),
-            first_number: None,
-            second_number: None,
-            operator: None,
             input_mode: InputMode::Normal,
             messages: Vec::new(),
             character_index: 0,
+            history: Vec::new(),
+            history_index: None,
+            context_menu: ContextMenu::new(),
+            theme: Theme::dark(),
         }
     }
 
@@ -50,24 +55,124 @@ impl App {
         first - second
     }
     #[allow(clippy::must_use_candidate)]
+    pub const fn mul(first: i32, second: i32) -> i32 {
+        first * second
+    }
+    #[allow(clippy::must_use_candidate)]
     pub const fn div(first: i32, second: i32) -> i32 {
         first / second
     }
+    #[allow(clippy::must_use_candidate)]
+    pub const fn and(first: i32, second: i32) -> i32 {
+        first & second
+    }
+    #[allow(clippy::must_use_candidate)]
+    pub const fn or(first: i32, second: i32) -> i32 {
+        first | second
+    }
+    #[allow(clippy::must_use_candidate)]
+    pub const fn xor(first: i32, second: i32) -> i32 {
+        first ^ second
+    }
+    #[allow(clippy::must_use_candidate)]
+    pub const fn not(first: i32) -> i32 {
+        !first
+    }
+    #[allow(clippy::must_use_candidate)]
+    pub const fn shl(first: i32, second: i32) -> i32 {
+        first << second
+    }
+    #[allow(clippy::must_use_candidate)]
+    pub const fn shr(first: i32, second: i32) -> i32 {
+        first >> second
+    }
 
     fn move_cursor_left(&mut self) {
         let cursor_moved_left = self.character_index.saturating_sub(1);
         self.character_index = self.clamp_cursor(cursor_moved_left);
+        self.refresh_context_menu();
     }
 
     fn move_cursor_right(&mut self) {
         let cursor_moved_right = self.character_index.saturating_add(1);
         self.character_index = self.clamp_cursor(cursor_moved_right);
+        self.refresh_context_menu();
     }
 
     fn enter_char(&mut self, new_char: char) {
         let index = self.byte_index();
         self.input.insert(index, new_char);
         self.move_cursor_right();
+        self.history_index = None;
+        self.refresh_context_menu();
+    }
+
+    fn load_history_entry(&mut self, index: usize) {
+        self.history_index = Some(index);
+        self.input = self.history[index].clone();
+        self.character_index = self.input.chars().count();
+        self.refresh_context_menu();
+    }
+
+    fn history_prev(&mut self) {
+        if self.history.is_empty() {
+            return;
+        }
+        let new_index = match self.history_index {
+            Some(index) => index.saturating_sub(1),
+            None => self.history.len() - 1,
+        };
+        self.load_history_entry(new_index);
+    }
+
+    fn history_next(&mut self) {
+        let Some(index) = self.history_index else {
+            return;
+        };
+        if index + 1 < self.history.len() {
+            self.load_history_entry(index + 1);
+        } else {
+            self.history_index = None;
+            self.input.clear();
+            self.reset_cursor();
+            self.refresh_context_menu();
+        }
+    }
+
+    /// The whitespace-delimited token immediately before the cursor,
+    /// i.e. whatever operator the user might currently be typing.
+    fn current_token(&self) -> &str {
+        let before_cursor = &self.input[..self.byte_index()];
+        let start = before_cursor
+            .rfind(char::is_whitespace)
+            .map_or(0, |i| i + 1);
+        &before_cursor[start..]
+    }
+
+    /// Recompute the operator-autocomplete overlay for the token under
+    /// the cursor, closing it for empty or purely numeric tokens.
+    fn refresh_context_menu(&mut self) {
+        let token = self.current_token().to_string();
+        if token.is_empty() || token.chars().all(|c| c.is_ascii_digit()) {
+            self.context_menu.close();
+        } else {
+            self.context_menu.update(&token);
+        }
+    }
+
+    /// Replace the partial operator token under the cursor with the
+    /// currently selected autocomplete candidate.
+    fn commit_context_menu_selection(&mut self) {
+        let Some(symbol) = self.context_menu.selected() else {
+            self.context_menu.close();
+            return;
+        };
+
+        let end = self.byte_index();
+        let start = end - self.current_token().len();
+        self.input.replace_range(start..end, symbol);
+        self.character_index = self.input[..start + symbol.len()].chars().count();
+        self.context_menu.close();
     }
 
     fn byte_index(&self) -> usize {
@@ -100,65 +205,30 @@ impl App {
     }
 
     fn submit_message(&mut self) {
-        if self.first_number.is_none() {
-            if let Ok(num) = self.input.trim().parse::<i32>() {
-                self.first_number = Some(num);
-                self.messages.push(format!("First number entered: {num}"));
-            } else {
-                self.messages
-                    .push("Invalid input! Please enter a valid first number.".to_string());
-            }
-        } else if self.second_number.is_none() {
-            if let Ok(num) = self.input.trim().parse::<i32>() {
-                self.second_number = Some(num);
-                self.messages.push(format!("Second number entered: {num}"));
-            } else {
-                self.messages
-                    .push("Invalid input! Please enter a valid second number.".to_string());
-            }
-        } else if self.operator.is_none() {
-            let trimmed_input = self.input.trim().to_lowercase();
-            match trimmed_input.as_str() {
-                "+" | "plus" => self.operator = Some("+".to_string()),
-                "-" | "minus" => self.operator = Some("-".to_string()),
-                "/" | "div" => self.operator = Some("/".to_string()),
-                _ => {
-                    self.messages.push("Invalid operator! Please enter a valid operator: '+' (plus), '-' (minus), or '/' (div).".to_string());
-                }
-            }
+        let trimmed = self.input.trim().to_string();
+        if !trimmed.is_empty() {
+            self.history.push(trimmed.clone());
         }
+        self.history_index = None;
 
-        if let (Some(first), Some(second), Some(operator)) =
-            (self.first_number, self.second_number, &self.operator)
-        {
-            let result = match operator.as_str() {
-                "+" => Self::add(first, second),
-                "-" => Self::subtract(first, second),
-                "/" => Self::div(first, second),
-                _ => unreachable!(),
-            };
-
-            self.messages
-                .push(format!("{first} {operator} {second} = {result}"));
-
-            self.first_number = None;
-            self.second_number = None;
-            self.operator = None;
+        match expr::evaluate(&trimmed) {
+            Ok(result) => self.messages.push(format!("{trimmed} = {result}")),
+            Err(_) => self.messages.push("Invalid".to_string()),
         }
 
         self.input.clear();
         self.reset_cursor();
+        self.context_menu.close();
     }
 
     fn clear_messages(&mut self) {
         self.messages.clear();
-        self.first_number = None;
-        self.second_number = None;
-        self.operator = None;
     }
 
     #[allow(clippy::missing_errors_doc)]
-    pub fn run(mut self, mut terminal: DefaultTerminal) -> Result<()> {
+    pub fn run<B: Backend>(mut self, mut terminal: Terminal<B>) -> Result<()> {
+        self.theme = Theme::detect();
+
         loop {
             terminal.draw(|frame| self.draw(frame))?;
 
@@ -174,17 +244,40 @@ impl App {
                         KeyCode::Char('c') => {
                             self.clear_messages();
                         }
+                        KeyCode::Char('t') => {
+                            self.theme = self.theme.cycle();
+                        }
                         _ => {}
                     },
-                    InputMode::Editing if key.kind == KeyEventKind::Press => match key.code {
-                        KeyCode::Enter => self.submit_message(),
-                        KeyCode::Char(to_insert) => self.enter_char(to_insert),
-                        KeyCode::Backspace => self.delete_char(),
-                        KeyCode::Left => self.move_cursor_left(),
-                        KeyCode::Right => self.move_cursor_right(),
-                        KeyCode::Esc => self.input_mode = InputMode::Normal,
-                        _ => {}
-                    },
+                    InputMode::Editing if key.kind == KeyEventKind::Press => {
+                        if self.context_menu.active {
+                            match key.code {
+                                KeyCode::Up => self.context_menu.move_up(),
+                                KeyCode::Down => self.context_menu.move_down(),
+                                KeyCode::Tab | KeyCode::Enter => {
+                                    self.commit_context_menu_selection();
+                                }
+                                KeyCode::Char(to_insert) => self.enter_char(to_insert),
+                                KeyCode::Backspace => self.delete_char(),
+                                KeyCode::Left => self.move_cursor_left(),
+                                KeyCode::Right => self.move_cursor_right(),
+                                KeyCode::Esc => self.context_menu.close(),
+                                _ => {}
+                            }
+                        } else {
+                            match key.code {
+                                KeyCode::Enter => self.submit_message(),
+                                KeyCode::Char(to_insert) => self.enter_char(to_insert),
+                                KeyCode::Backspace => self.delete_char(),
+                                KeyCode::Left => self.move_cursor_left(),
+                                KeyCode::Right => self.move_cursor_right(),
+                                KeyCode::Up => self.history_prev(),
+                                KeyCode::Down => self.history_next(),
+                                KeyCode::Esc => self.input_mode = InputMode::Normal,
+                                _ => {}
+                            }
+                        }
+                    }
                     InputMode::Editing => {}
                 }
             }
@@ -195,7 +288,7 @@ impl App {
         // Create the big text
         let big_text = BigText::builder()
             .pixel_size(PixelSize::HalfHeight)
-            .style(Style::new().blue())
+            .style(self.theme.title)
             .lines(vec!["Bitwise-Calc".into()])
             .build();
 
@@ -218,49 +311,37 @@ impl App {
             InputMode::Normal => (
                 vec![
                     Line::from(vec![
-                        Span::styled("'q'", Style::default().fg(Color::Red)),
+                        Span::styled("'q'", self.theme.error),
                         Span::raw(" to exit"),
                     ]),
                     Line::from(vec![
-                        Span::styled("'e'", Style::default().fg(Color::Yellow)),
+                        Span::styled("'e'", self.theme.prompt),
                         Span::raw(" to start editing"),
                     ]),
                     Line::from(vec![
-                        Span::styled("'c'", Style::default().fg(Color::LightBlue)),
+                        Span::styled("'c'", self.theme.hint),
                         Span::raw(" to clear messages"),
                     ]),
+                    Line::from(vec![
+                        Span::styled("'t'", self.theme.hint),
+                        Span::raw(format!(" to cycle theme ({})", self.theme.name)),
+                    ]),
                 ],
-                RatatuiStyle::default().add_modifier(Modifier::RAPID_BLINK),
+                Style::default().add_modifier(Modifier::RAPID_BLINK),
             ),
             InputMode::Editing => {
-                let prompt = if self.first_number.is_none() {
-                    vec![Line::from(Span::styled(
-                        "Please enter the first number",
-                        Style::default().fg(Color::Yellow),
-                    ))]
-                } else if self.second_number.is_none() {
-                    vec![Line::from(Span::styled(
-                        "Please enter the second number",
-                        Style::default().fg(Color::Yellow),
-                    ))]
-                } else {
-                    vec![
-                        Line::from(Span::styled(
-                            "Please enter your operation",
-                            Style::default().fg(Color::Yellow),
-                        )),
-                        Line::from(Span::styled(
-                            "➣ '+' or 'plus'",
-                            Style::default().fg(Color::Green),
-                        )),
-                        Line::from(Span::styled(
-                            "➣ '-' or 'minus'",
-                            Style::default().fg(Color::Green),
-                        )),
-                    ]
-                };
-
-                (prompt, RatatuiStyle::default())
+                let prompt = vec![
+                    Line::from(Span::styled(
+                        "Please enter an expression, e.g. '3 + 4 << 2 & 7'",
+                        self.theme.prompt,
+                    )),
+                    Line::from(Span::styled(
+                        "➣ operators: + - * / & | ^ ~ << >>",
+                        self.theme.hint,
+                    )),
+                ];
+
+                (prompt, Style::default())
             }
         };
 
@@ -272,8 +353,8 @@ impl App {
         // Input area
         let input = Paragraph::new(self.input.as_str())
             .style(match self.input_mode {
-                InputMode::Normal => RatatuiStyle::default(),
-                InputMode::Editing => RatatuiStyle::default().fg(Color::Yellow),
+                InputMode::Normal => Style::default(),
+                InputMode::Editing => self.theme.prompt,
             })
             .block(Block::bordered().title("Input"));
         frame.render_widget(input, input_area);
@@ -294,18 +375,56 @@ impl App {
             .enumerate()
             .rev()
             .map(|(i, m)| {
-                let content = if m.contains('=') {
-                    Span::styled(format!("{i}: {m}"), Style::default().fg(Color::Green))
+                let style = if m.contains('=') {
+                    self.theme.result
                 } else if m.contains("Invalid") {
-                    Span::styled(format!("{i}: {m}"), Style::default().fg(Color::Red))
+                    self.theme.error
                 } else {
-                    Span::styled(format!("{i}: {m}"), Style::default().fg(Color::White))
+                    self.theme.normal_text
                 };
-                ListItem::new(Line::from(content))
+                ListItem::new(Line::from(Span::styled(format!("{i}: {m}"), style)))
             })
             .collect();
         let messages = List::new(messages).block(Block::bordered().title("Messages"));
         frame.render_widget(messages, messages_area);
+
+        // Operator autocomplete overlay, drawn last so it layers over the messages.
+        if self.context_menu.active {
+            self.draw_context_menu(frame, input_area);
+        }
+    }
+
+    #[allow(clippy::cast_possible_truncation)]
+    fn draw_context_menu(&self, frame: &mut RatatuiFrame, input_area: Rect) {
+        let frame_area = frame.area();
+        let menu_area = Rect {
+            x: input_area.x,
+            y: input_area.bottom(),
+            width: 30.min(frame_area.width.saturating_sub(input_area.x)),
+            height: (self.context_menu.items.len() as u16 + 2)
+                .min(frame_area.height.saturating_sub(input_area.bottom())),
+        };
+
+        let items: Vec<ListItem> = self
+            .context_menu
+            .items
+            .iter()
+            .enumerate()
+            .map(|(i, (symbol, word, description))| {
+                let style = if i == self.context_menu.row_pos {
+                    self.context_menu.selected_style
+                } else {
+                    self.context_menu.normal_style
+                };
+                ListItem::new(Line::from(Span::styled(
+                    format!("{symbol} ({word}) — {description}"),
+                    style,
+                )))
+            })
+            .collect();
+
+        let menu = List::new(items).block(Block::bordered().title("Operators"));
+        frame.render_widget(menu, menu_area);
     }
 }
 
@@ -333,22 +452,99 @@ mod tests {
     fn test_divide() {
         assert_eq!(App::div(6, 3), 2);
     }
+    #[test]
+    fn test_mul() {
+        assert_eq!(App::mul(6, 3), 18);
+    }
+    #[test]
+    fn test_and() {
+        assert_eq!(App::and(0b1100, 0b1010), 0b1000);
+    }
+    #[test]
+    fn test_or() {
+        assert_eq!(App::or(0b1100, 0b1010), 0b1110);
+    }
+    #[test]
+    fn test_xor() {
+        assert_eq!(App::xor(0b1100, 0b1010), 0b0110);
+    }
+    #[test]
+    fn test_not() {
+        assert_eq!(App::not(0), -1);
+    }
+    #[test]
+    fn test_shl() {
+        assert_eq!(App::shl(1, 4), 16);
+    }
+    #[test]
+    fn test_shr() {
+        assert_eq!(App::shr(16, 4), 1);
+    }
+
+    #[test]
+    fn test_history_navigation() {
+        let mut app = App::new();
+        app.history.push("1".to_string());
+        app.history.push("2".to_string());
+        app.history.push("3".to_string());
+
+        app.history_prev();
+        assert_eq!(app.input, "3");
+        app.history_prev();
+        assert_eq!(app.input, "2");
+        app.history_prev();
+        assert_eq!(app.input, "1");
+        app.history_prev();
+        assert_eq!(app.input, "1");
+
+        app.history_next();
+        assert_eq!(app.input, "2");
+        app.history_next();
+        assert_eq!(app.input, "3");
+        app.history_next();
+        assert_eq!(app.input, "");
+        assert!(app.history_index.is_none());
+    }
+
+    #[test]
+    fn test_context_menu_opens_for_partial_operator() {
+        let mut app = App::new();
+        for c in "3 x".chars() {
+            app.enter_char(c);
+        }
+        assert!(app.context_menu.active);
+        assert_eq!(app.context_menu.selected(), Some("^"));
+    }
+
+    #[test]
+    fn test_context_menu_closes_for_numeric_token() {
+        let mut app = App::new();
+        for c in "42".chars() {
+            app.enter_char(c);
+        }
+        assert!(!app.context_menu.active);
+    }
+
+    #[test]
+    fn test_commit_context_menu_selection_replaces_partial_token() {
+        let mut app = App::new();
+        for c in "3 x".chars() {
+            app.enter_char(c);
+        }
+        app.commit_context_menu_selection();
+        assert_eq!(app.input, "3 ^");
+        assert!(!app.context_menu.active);
+    }
 
     #[test]
     fn test_clear_messages() {
         let mut app = App::new();
 
-        app.first_number = Some(42);
-        app.second_number = Some(7);
-        app.operator = Some("+".to_string());
-        app.messages.push("First number entered: 42".to_string());
-        app.messages.push("Second number entered: 7".to_string());
+        app.messages.push("1 + 2 = 3".to_string());
+        app.messages.push("3 & 1 = 1".to_string());
 
         app.clear_messages();
 
-        assert!(app.first_number.is_none());
-        assert!(app.second_number.is_none());
-        assert!(app.operator.is_none());
         assert!(app.messages.is_empty());
     }
 }
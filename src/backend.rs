@@ -0,0 +1,55 @@
+//! Terminal backend selection.
+//!
+//! `App::run` is generic over any [`ratatui::backend::Backend`], so
+//! rendering itself doesn't care which terminal library drives it.
+//! The only backend actually wired up here is `ratatui`'s crossterm
+//! one, via [`init`] and [`restore`] below — plugging in another
+//! backend (e.g. `termion`) would mean adding its own `init`/`restore`
+//! pair *and* routing input through it, since [`crate::app::App::run`]
+//! still reads events via `ratatui::crossterm::event::read`.
+//!
+//! This only delivers the generic-rendering half of "pluggable backend
+//! selection via Cargo features" — no second backend is wired up, so
+//! treat that as still outstanding rather than done.
+
+use color_eyre::{config::HookBuilder, Result};
+use ratatui::backend::CrosstermBackend;
+use ratatui::Terminal;
+use std::io::Stdout;
+
+pub type Tui = Terminal<CrosstermBackend<Stdout>>;
+
+/// Install panic and error hooks that restore the terminal (leave the
+/// alternate screen, disable raw mode) via [`restore`] before printing
+/// the panic or error report, so a crash never leaves the shell stuck
+/// in a garbled state. Replaces a plain `color_eyre::install()` call.
+pub fn install_panic_hook() -> Result<()> {
+    let (panic_hook, eyre_hook) = HookBuilder::default().into_hooks();
+
+    let panic_hook = panic_hook.into_panic_hook();
+    std::panic::set_hook(Box::new(move |panic_info| {
+        let _ = restore();
+        panic_hook(panic_info);
+    }));
+
+    let eyre_hook = eyre_hook.into_eyre_hook();
+    color_eyre::eyre::set_hook(Box::new(move |error| {
+        let _ = restore();
+        eyre_hook(error)
+    }))?;
+
+    Ok(())
+}
+
+// Fallible to leave room for backends that can fail to initialize,
+// even though this one can't.
+#[allow(clippy::unnecessary_wraps)]
+pub fn init() -> Result<Tui> {
+    Ok(ratatui::init())
+}
+
+#[allow(clippy::unnecessary_wraps)]
+pub fn restore() -> Result<()> {
+    ratatui::restore();
+    Ok(())
+}
@@ -0,0 +1,132 @@
+use ratatui::style::{Color, Modifier, Style};
+
+/// Symbol / word-alias / description triples for every operator the
+/// expression evaluator understands, in the order they should be listed.
+/// The symbol is what gets inserted into the input; the word alias lets
+/// a candidate be found by typing e.g. `"x"` for `^`, and the expression
+/// evaluator also accepts it in place of the symbol (`"3 xor 4"`).
+pub const OPERATORS: &[(&str, &str, &str)] = &[
+    ("+", "plus", "Addition"),
+    ("-", "minus", "Subtraction"),
+    ("*", "times", "Multiplication"),
+    ("/", "div", "Division"),
+    ("&", "and", "Bitwise AND"),
+    ("|", "or", "Bitwise OR"),
+    ("^", "xor", "Bitwise XOR"),
+    ("~", "not", "Bitwise NOT (unary)"),
+    ("<<", "shl", "Left shift"),
+    (">>", "shr", "Right shift"),
+];
+
+/// Autocomplete overlay that suggests operators matching whatever
+/// partial token the user is currently typing.
+pub struct ContextMenu {
+    pub items: Vec<(&'static str, &'static str, &'static str)>,
+    pub row_pos: usize,
+    pub active: bool,
+    pub selected_style: Style,
+    pub normal_style: Style,
+}
+
+impl Default for ContextMenu {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ContextMenu {
+    pub const fn new() -> Self {
+        Self {
+            items: Vec::new(),
+            row_pos: 0,
+            active: false,
+            selected_style: Style::new()
+                .fg(Color::Black)
+                .bg(Color::Yellow)
+                .add_modifier(Modifier::BOLD),
+            normal_style: Style::new(),
+        }
+    }
+
+    /// Recompute the candidate list for the current partial operator
+    /// token, e.g. `"x"` narrows the list down to `"xor"` (`^`).
+    pub fn update(&mut self, prefix: &str) {
+        if prefix.is_empty() {
+            self.items.clear();
+            self.active = false;
+            self.row_pos = 0;
+            return;
+        }
+
+        self.items = OPERATORS
+            .iter()
+            .copied()
+            .filter(|(symbol, word, _)| symbol.starts_with(prefix) || word.starts_with(prefix))
+            .collect();
+        self.active = !self.items.is_empty();
+        self.row_pos = self.row_pos.min(self.items.len().saturating_sub(1));
+    }
+
+    pub fn move_up(&mut self) {
+        self.row_pos = self.row_pos.saturating_sub(1);
+    }
+
+    pub fn move_down(&mut self) {
+        if self.row_pos + 1 < self.items.len() {
+            self.row_pos += 1;
+        }
+    }
+
+    pub fn selected(&self) -> Option<&'static str> {
+        self.items.get(self.row_pos).map(|(symbol, ..)| *symbol)
+    }
+
+    pub fn close(&mut self) {
+        self.active = false;
+        self.items.clear();
+        self.row_pos = 0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_update_filters_by_symbol_prefix() {
+        let mut menu = ContextMenu::new();
+        menu.update("<");
+        assert_eq!(menu.items, vec![("<<", "shl", "Left shift")]);
+    }
+
+    #[test]
+    fn test_update_filters_by_word_prefix() {
+        let mut menu = ContextMenu::new();
+        menu.update("x");
+        assert_eq!(menu.items, vec![("^", "xor", "Bitwise XOR")]);
+    }
+
+    #[test]
+    fn test_empty_prefix_closes_menu() {
+        let mut menu = ContextMenu::new();
+        menu.update("x");
+        menu.update("");
+        assert!(!menu.active);
+        assert!(menu.items.is_empty());
+    }
+
+    #[test]
+    fn test_move_up_and_down_clamp() {
+        let mut menu = ContextMenu::new();
+        menu.update("s"); // matches "shl" and "shr"
+        assert_eq!(menu.items.len(), 2);
+
+        menu.move_up();
+        assert_eq!(menu.row_pos, 0);
+
+        menu.move_down();
+        assert_eq!(menu.row_pos, 1);
+        menu.move_down();
+        assert_eq!(menu.row_pos, 1);
+    }
+}
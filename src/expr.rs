@@ -0,0 +1,249 @@
+//! Infix expression evaluation via the shunting-yard algorithm.
+//!
+//! Fixed operator precedence, highest to lowest:
+//! `~` `u-` (unary) > `<<` `>>` > `&` > `^` > `|` > `*` `/` > `+` `-`.
+//! Operators of equal precedence are left-associative, except the unary
+//! operators, which bind only to the value immediately to their right.
+//! A `-` tokenizes as unary negation (`u-`) when it starts the input or
+//! immediately follows another operator; otherwise it's subtraction.
+
+use crate::app::App;
+use crate::context_menu::OPERATORS;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Token {
+    Num(i32),
+    Op(String),
+}
+
+pub fn evaluate(input: &str) -> Result<i32, String> {
+    let tokens = tokenize(input)?;
+    let rpn = to_rpn(tokens);
+    eval_rpn(&rpn)
+}
+
+fn tokenize(input: &str) -> Result<Vec<Token>, String> {
+    let mut tokens = Vec::new();
+    let mut chars = input.chars().peekable();
+
+    while let Some(&c) = chars.peek() {
+        if c.is_whitespace() {
+            chars.next();
+        } else if c.is_ascii_digit() {
+            let mut number = String::new();
+            while let Some(&digit) = chars.peek() {
+                if digit.is_ascii_digit() {
+                    number.push(digit);
+                    chars.next();
+                } else {
+                    break;
+                }
+            }
+            let value = number.parse::<i32>().map_err(|_| "Invalid".to_string())?;
+            tokens.push(Token::Num(value));
+        } else if c == '<' || c == '>' {
+            chars.next();
+            if chars.next_if_eq(&c).is_some() {
+                tokens.push(Token::Op(format!("{c}{c}")));
+            } else {
+                return Err("Invalid".to_string());
+            }
+        } else if "+-*/&|^~".contains(c) {
+            chars.next();
+            push_operator(&mut tokens, &c.to_string());
+        } else if c.is_alphabetic() {
+            let mut word = String::new();
+            while let Some(&letter) = chars.peek() {
+                if letter.is_alphabetic() {
+                    word.push(letter);
+                    chars.next();
+                } else {
+                    break;
+                }
+            }
+            let symbol = OPERATORS
+                .iter()
+                .find(|(_, alias, _)| *alias == word)
+                .map(|(symbol, ..)| *symbol)
+                .ok_or_else(|| "Invalid".to_string())?;
+            push_operator(&mut tokens, symbol);
+        } else {
+            return Err("Invalid".to_string());
+        }
+    }
+
+    Ok(tokens)
+}
+
+/// Push `symbol` onto `tokens`, recognizing `-` as unary negation (`u-`)
+/// rather than subtraction when it starts the input or immediately
+/// follows another operator, since neither case has a left-hand value
+/// to subtract from. Shared by both the symbol and word-alias branches
+/// of [`tokenize`], e.g. `"-"` and `"minus"` are equivalent input.
+fn push_operator(tokens: &mut Vec<Token>, symbol: &str) {
+    if symbol == "-" && !matches!(tokens.last(), Some(Token::Num(_))) {
+        tokens.push(Token::Op("u-".to_string()));
+    } else {
+        tokens.push(Token::Op(symbol.to_string()));
+    }
+}
+
+fn precedence(op: &str) -> u8 {
+    match op {
+        "~" | "u-" => 6,
+        "<<" | ">>" => 5,
+        "&" => 4,
+        "^" => 3,
+        "|" => 2,
+        "*" | "/" => 1,
+        _ => 0, // '+' and '-'
+    }
+}
+
+fn is_unary(op: &str) -> bool {
+    op == "~" || op == "u-"
+}
+
+fn to_rpn(tokens: Vec<Token>) -> Vec<Token> {
+    let mut output = Vec::new();
+    let mut operators: Vec<String> = Vec::new();
+
+    for token in tokens {
+        match token {
+            Token::Num(_) => output.push(token),
+            Token::Op(op) => {
+                if !is_unary(&op) {
+                    while let Some(top) = operators.last() {
+                        if precedence(top) >= precedence(&op) {
+                            output.push(Token::Op(operators.pop().unwrap_or_default()));
+                        } else {
+                            break;
+                        }
+                    }
+                }
+                operators.push(op);
+            }
+        }
+    }
+
+    while let Some(op) = operators.pop() {
+        output.push(Token::Op(op));
+    }
+
+    output
+}
+
+fn eval_rpn(tokens: &[Token]) -> Result<i32, String> {
+    let mut stack: Vec<i32> = Vec::new();
+
+    for token in tokens {
+        match token {
+            Token::Num(n) => stack.push(*n),
+            Token::Op(op) if op == "~" => {
+                let value = stack.pop().ok_or_else(|| "Invalid".to_string())?;
+                stack.push(App::not(value));
+            }
+            Token::Op(op) if op == "u-" => {
+                let value = stack.pop().ok_or_else(|| "Invalid".to_string())?;
+                stack.push(App::subtract(0, value));
+            }
+            Token::Op(op) => {
+                let rhs = stack.pop().ok_or_else(|| "Invalid".to_string())?;
+                let lhs = stack.pop().ok_or_else(|| "Invalid".to_string())?;
+                stack.push(apply(op, lhs, rhs)?);
+            }
+        }
+    }
+
+    match stack.as_slice() {
+        [result] => Ok(*result),
+        _ => Err("Invalid".to_string()),
+    }
+}
+
+fn apply(op: &str, lhs: i32, rhs: i32) -> Result<i32, String> {
+    match op {
+        "+" => Ok(App::add(lhs, rhs)),
+        "-" => Ok(App::subtract(lhs, rhs)),
+        "*" => Ok(App::mul(lhs, rhs)),
+        "/" if rhs == 0 => Err("Invalid".to_string()),
+        "/" => Ok(App::div(lhs, rhs)),
+        "&" => Ok(App::and(lhs, rhs)),
+        "|" => Ok(App::or(lhs, rhs)),
+        "^" => Ok(App::xor(lhs, rhs)),
+        "<<" | ">>" if !(0..32).contains(&rhs) => Err("Invalid".to_string()),
+        "<<" => Ok(App::shl(lhs, rhs)),
+        ">>" => Ok(App::shr(lhs, rhs)),
+        _ => Err("Invalid".to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_precedence_order() {
+        // Operands chosen so the `&`-binds-tighter and `+`-binds-tighter
+        // groupings disagree (14 vs. 0), unlike the coincidentally
+        // symmetric operands this test used to assert on.
+        assert_eq!(evaluate("10 + 6 & 12"), Ok(10 + (6 & 12)));
+    }
+
+    #[test]
+    fn test_simple_addition() {
+        assert_eq!(evaluate("1 + 2"), Ok(3));
+    }
+
+    #[test]
+    fn test_unary_not() {
+        assert_eq!(evaluate("~0"), Ok(-1));
+    }
+
+    #[test]
+    fn test_leading_unary_minus() {
+        assert_eq!(evaluate("-5"), Ok(-5));
+    }
+
+    #[test]
+    fn test_unary_minus_after_operator() {
+        assert_eq!(evaluate("3 + -5"), Ok(-2));
+    }
+
+    #[test]
+    fn test_binary_minus_still_subtracts() {
+        assert_eq!(evaluate("3 - 5"), Ok(-2));
+    }
+
+    #[test]
+    fn test_word_alias_operators() {
+        assert_eq!(evaluate("3 and 4"), Ok(3 & 4));
+        assert_eq!(evaluate("1 shl 2"), Ok(1 << 2));
+        assert_eq!(evaluate("not 0"), Ok(!0));
+    }
+
+    #[test]
+    fn test_unknown_word_is_invalid() {
+        assert_eq!(evaluate("3 foo 4"), Err("Invalid".to_string()));
+    }
+
+    #[test]
+    fn test_division_by_zero_is_invalid() {
+        assert_eq!(evaluate("1 / 0"), Err("Invalid".to_string()));
+    }
+
+    #[test]
+    fn test_shift_overflow_is_invalid() {
+        assert_eq!(evaluate("1 << 32"), Err("Invalid".to_string()));
+    }
+
+    #[test]
+    fn test_unbalanced_expression_is_invalid() {
+        assert_eq!(evaluate("1 +"), Err("Invalid".to_string()));
+    }
+
+    #[test]
+    fn test_bad_token_is_invalid() {
+        assert_eq!(evaluate("1 @ 2"), Err("Invalid".to_string()));
+    }
+}
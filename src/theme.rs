@@ -0,0 +1,86 @@
+use ratatui::style::{Color, Style};
+
+/// Named style slots used throughout [`crate::app::App::draw`], so a
+/// theme swap never means hunting down inline `Color::*` values.
+#[derive(Clone, Copy)]
+pub struct Theme {
+    pub name: &'static str,
+    pub title: Style,
+    pub prompt: Style,
+    pub hint: Style,
+    pub result: Style,
+    pub error: Style,
+    pub normal_text: Style,
+}
+
+impl Theme {
+    pub const fn dark() -> Self {
+        Self {
+            name: "dark",
+            title: Style::new().fg(Color::Blue),
+            prompt: Style::new().fg(Color::Yellow),
+            hint: Style::new().fg(Color::LightBlue),
+            result: Style::new().fg(Color::Green),
+            error: Style::new().fg(Color::Red),
+            normal_text: Style::new().fg(Color::White),
+        }
+    }
+
+    pub const fn light() -> Self {
+        Self {
+            name: "light",
+            title: Style::new().fg(Color::Blue),
+            prompt: Style::new().fg(Color::Magenta),
+            hint: Style::new().fg(Color::Blue),
+            result: Style::new().fg(Color::Green),
+            error: Style::new().fg(Color::Red),
+            normal_text: Style::new().fg(Color::Black),
+        }
+    }
+
+    const PRESETS: [fn() -> Self; 2] = [Self::dark, Self::light];
+
+    /// Advance to the next preset, wrapping back to the first.
+    #[must_use]
+    pub fn cycle(self) -> Self {
+        let current = Self::PRESETS
+            .iter()
+            .position(|preset| preset().name == self.name)
+            .unwrap_or(0);
+        Self::PRESETS[(current + 1) % Self::PRESETS.len()]()
+    }
+
+    /// Guess whether the terminal has a light or dark background from
+    /// the `COLORFGBG` environment variable (`"fg;bg"`, set by most
+    /// terminal emulators), falling back to the dark theme when the
+    /// variable is absent or unparseable.
+    #[must_use]
+    pub fn detect() -> Self {
+        std::env::var("COLORFGBG")
+            .ok()
+            .and_then(|value| {
+                // ANSI indices 0-6 and 8 are the dark half of the
+                // standard palette; everything else reads as light.
+                let bg = value.split(';').next_back()?.parse::<u8>().ok()?;
+                Some(if matches!(bg, 0..=6 | 8) {
+                    Self::dark()
+                } else {
+                    Self::light()
+                })
+            })
+            .unwrap_or_else(Self::dark)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cycle_wraps_between_presets() {
+        let dark = Theme::dark();
+        let light = dark.cycle();
+        assert_eq!(light.name, "light");
+        assert_eq!(light.cycle().name, "dark");
+    }
+}